@@ -11,11 +11,14 @@ use aws_smithy_eventstream::frame::{
 use bytes::Buf;
 use bytes::Bytes;
 use bytes_utils::SegmentedBuf;
+use futures_core::Stream;
 use hyper::body::HttpBody;
 use std::error::Error as StdError;
 use std::fmt;
 use std::marker::PhantomData;
 use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 
 /// Wrapper around SegmentedBuf that tracks the state of the stream.
 #[derive(Debug)]
@@ -43,6 +46,14 @@ impl RecvBuf {
         matches!(self, RecvBuf::EosPartial(_))
     }
 
+    /// Returns the number of bytes currently buffered but not yet decoded into a frame.
+    fn buffered_len(&self) -> usize {
+        match self {
+            RecvBuf::Empty => 0,
+            RecvBuf::Partial(segments) | RecvBuf::EosPartial(segments) => segments.remaining(),
+        }
+    }
+
     /// Returns a mutable reference to the underlying buffered data.
     fn buffered(&mut self) -> &mut SegmentedBuf<Bytes> {
         match self {
@@ -79,6 +90,15 @@ impl RecvBuf {
             RecvBuf::EosPartial(_) => panic!("already end of stream; this is a bug"),
         }
     }
+
+    /// Returns a `RecvBuf` that has reached end of stream because of a cooperative stop
+    /// request, discarding any bytes buffered for the current in-progress (and now abandoned)
+    /// frame. This is deliberately different from [`RecvBuf::ended`], which preserves a
+    /// leftover partial frame so it can be reported as [`Error::UnexpectedEndOfStream`]: a
+    /// requested stop should end the stream cleanly instead.
+    fn stopped(self) -> Self {
+        RecvBuf::EosPartial(SegmentedBuf::new())
+    }
 }
 
 /// Raw message from a [`Receiver`] when a [`SdkError::ResponseError`] is returned.
@@ -88,7 +108,8 @@ pub enum RawMessage {
     /// Message was decoded into a valid frame, but failed to unmarshall into a modeled type.
     Decoded(Message),
     /// Message failed to be decoded into a valid frame. The raw bytes may not be available in the
-    /// case where decoding consumed the buffer.
+    /// case where decoding consumed the buffer, unless [`Receiver::with_raw_capture`] was used
+    /// to enable keeping a copy of the in-progress frame around for diagnostics.
     Invalid(Option<Bytes>),
 }
 
@@ -104,18 +125,223 @@ pub enum Error {
     /// The stream ended before a complete message frame was received.
     #[non_exhaustive]
     UnexpectedEndOfStream,
+    /// The message's `content-encoding` header named an encoding that isn't recognized, or
+    /// isn't enabled via [`Receiver::with_payload_decompression`].
+    #[non_exhaustive]
+    UnsupportedContentEncoding(String),
+    /// Decompressing the message payload failed.
+    #[non_exhaustive]
+    DecompressionFailed(DecompressionError),
+    /// Buffering a message frame exceeded the configured limit before the frame was complete.
+    /// See [`Receiver::with_max_frame_buffer`].
+    #[non_exhaustive]
+    BufferLimitExceeded {
+        /// The configured limit, in bytes.
+        limit: usize,
+        /// The number of bytes buffered when the limit was exceeded.
+        buffered: usize,
+    },
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::UnexpectedEndOfStream => write!(f, "unexpected end of stream"),
+            Self::UnsupportedContentEncoding(encoding) => write!(
+                f,
+                "unsupported or disabled content-encoding: `{}`",
+                encoding
+            ),
+            Self::DecompressionFailed(err) => write!(f, "failed to decompress payload: {}", err),
+            Self::BufferLimitExceeded { limit, buffered } => write!(
+                f,
+                "exceeded the {}-byte frame buffer limit ({} bytes buffered) before a complete \
+                 message frame was received",
+                limit, buffered
+            ),
         }
     }
 }
 
 impl StdError for Error {}
 
+/// A content-encoding that [`Receiver`] can be configured to transparently decompress.
+///
+/// See [`Receiver::with_payload_decompression`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ContentEncoding {
+    /// The `gzip` content-encoding. Requires the `event-stream-gzip` cargo feature.
+    Gzip,
+    /// The `deflate` content-encoding. Requires the `event-stream-gzip` cargo feature.
+    Deflate,
+    /// The `br` (Brotli) content-encoding. Requires the `event-stream-brotli` cargo feature.
+    Brotli,
+}
+
+impl ContentEncoding {
+    fn from_header_value(value: &str) -> Option<Self> {
+        match value {
+            "gzip" => Some(Self::Gzip),
+            "deflate" => Some(Self::Deflate),
+            "br" => Some(Self::Brotli),
+            _ => None,
+        }
+    }
+
+    fn decompress(self, payload: &[u8]) -> Result<Vec<u8>, DecompressionError> {
+        match self {
+            Self::Gzip => decompress_gzip(payload),
+            Self::Deflate => decompress_deflate(payload),
+            Self::Brotli => decompress_brotli(payload),
+        }
+    }
+}
+
+/// Error occurring while decompressing an event payload.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DecompressionError {
+    /// The compressed payload could not be decoded.
+    Io(std::io::Error),
+    /// The encoding was recognized, but its cargo feature was not enabled at build time.
+    FeatureNotEnabled(&'static str),
+}
+
+impl fmt::Display for DecompressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::FeatureNotEnabled(feature) => {
+                write!(f, "the `{}` cargo feature is not enabled", feature)
+            }
+        }
+    }
+}
+
+impl StdError for DecompressionError {}
+
+/// Default limit, in bytes, on how much data a [`Receiver`] will buffer while waiting for a
+/// complete message frame to be decoded. Derived from the event-stream protocol's maximum
+/// message size (16 MiB) plus some headroom for prelude/header overhead.
+///
+/// See [`Receiver::with_max_frame_buffer`].
+pub const DEFAULT_MAX_FRAME_BUFFER_BYTES: usize = 16 * 1024 * 1024 + 4096;
+
+#[derive(Debug, Default)]
+struct StopHandleInner {
+    stopped: std::sync::atomic::AtomicBool,
+    // `futures-util` (which would otherwise give us `task::AtomicWaker`) is only a
+    // dev-dependency of this crate, so the parked task's waker is tracked by hand instead.
+    waker: std::sync::Mutex<Option<std::task::Waker>>,
+}
+
+/// A cloneable handle for cooperatively stopping a [`Receiver`] from another task.
+///
+/// Dropping a `Receiver` abandons the underlying body without a clean teardown, and gives the
+/// caller no way to distinguish "I asked to stop" from a genuine transport error. `StopHandle`
+/// fixes that: calling [`StopHandle::stop`] causes the next `recv`/`poll_next` call to stop
+/// reading new chunks from the body, flush any message that had already been fully decoded,
+/// and then report end-of-stream via `Ok(None)`. If the `Receiver` is currently parked waiting
+/// on the body (e.g. an idle streaming RPC), `stop()` wakes it immediately rather than waiting
+/// for the body to otherwise produce data, an error, or EOF.
+#[derive(Debug, Clone, Default)]
+pub struct StopHandle {
+    inner: std::sync::Arc<StopHandleInner>,
+}
+
+impl StopHandle {
+    /// Requests that the associated [`Receiver`] stop reading new chunks from its body, waking
+    /// it immediately if it's currently parked waiting on the body.
+    pub fn stop(&self) {
+        self.inner
+            .stopped
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        if let Some(waker) = self.inner.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.inner.stopped.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Registers the current task to be woken by a future call to [`StopHandle::stop`].
+    fn register(&self, cx: &Context<'_>) {
+        let mut waker = self.inner.waker.lock().unwrap();
+        if !matches!(&*waker, Some(existing) if existing.will_wake(cx.waker())) {
+            *waker = Some(cx.waker().clone());
+        }
+    }
+}
+
+// NOTE: enabling `event-stream-gzip` or `event-stream-brotli` also requires adding `flate2`
+// and `brotli_decompressor` as optional dependencies wired to those features in this crate's
+// Cargo.toml. This checkout doesn't carry a Cargo.toml to edit, so that wiring still needs to
+// land alongside these cfg-gated functions wherever the manifest lives.
+#[cfg(feature = "event-stream-gzip")]
+fn decompress_gzip(payload: &[u8]) -> Result<Vec<u8>, DecompressionError> {
+    use std::io::Read;
+    let mut decompressed = Vec::new();
+    flate2::read::GzDecoder::new(payload)
+        .read_to_end(&mut decompressed)
+        .map_err(DecompressionError::Io)?;
+    Ok(decompressed)
+}
+#[cfg(not(feature = "event-stream-gzip"))]
+fn decompress_gzip(_payload: &[u8]) -> Result<Vec<u8>, DecompressionError> {
+    Err(DecompressionError::FeatureNotEnabled("event-stream-gzip"))
+}
+
+#[cfg(feature = "event-stream-gzip")]
+fn decompress_deflate(payload: &[u8]) -> Result<Vec<u8>, DecompressionError> {
+    use std::io::Read;
+    let mut decompressed = Vec::new();
+    flate2::read::DeflateDecoder::new(payload)
+        .read_to_end(&mut decompressed)
+        .map_err(DecompressionError::Io)?;
+    Ok(decompressed)
+}
+#[cfg(not(feature = "event-stream-gzip"))]
+fn decompress_deflate(_payload: &[u8]) -> Result<Vec<u8>, DecompressionError> {
+    Err(DecompressionError::FeatureNotEnabled("event-stream-gzip"))
+}
+
+#[cfg(feature = "event-stream-brotli")]
+fn decompress_brotli(payload: &[u8]) -> Result<Vec<u8>, DecompressionError> {
+    let mut decompressed = Vec::new();
+    brotli_decompressor::BrotliDecompress(&mut std::io::Cursor::new(payload), &mut decompressed)
+        .map_err(DecompressionError::Io)?;
+    Ok(decompressed)
+}
+#[cfg(not(feature = "event-stream-brotli"))]
+fn decompress_brotli(_payload: &[u8]) -> Result<Vec<u8>, DecompressionError> {
+    Err(DecompressionError::FeatureNotEnabled("event-stream-brotli"))
+}
+
+/// Returns the value of the message's content-encoding header, checking both the `:`-prefixed
+/// event-stream convention and the plain HTTP header name.
+fn content_encoding_header(message: &Message) -> Option<&str> {
+    message
+        .headers()
+        .iter()
+        .find(|h| matches!(h.name().as_str(), ":content-encoding" | "content-encoding"))
+        .and_then(|h| h.value().as_string().ok())
+        .map(|s| s.as_str())
+}
+
+/// Rebuilds `message` with `payload` in place of its original payload, and with the
+/// content-encoding header removed since the payload is no longer encoded.
+fn strip_content_encoding(message: &Message, payload: Bytes) -> Message {
+    message
+        .headers()
+        .iter()
+        .filter(|h| !matches!(h.name().as_str(), ":content-encoding" | "content-encoding"))
+        .fold(Message::new(payload), |message, header| {
+            message.add_header(header.clone())
+        })
+}
+
 /// Receives Smithy-modeled messages out of an Event Stream.
 #[derive(Debug)]
 pub struct Receiver<T, E> {
@@ -128,6 +354,24 @@ pub struct Receiver<T, E> {
     /// initial response, then the message will be stored in `buffered_message` so that it can
     /// be returned with the next call of `recv()`.
     buffered_message: Option<Message>,
+    /// Content-encodings that should be transparently decompressed, if any. `None` means
+    /// decompression is disabled, which is the default.
+    decompression: Option<std::collections::HashSet<ContentEncoding>>,
+    /// Maximum number of bytes that may be buffered while waiting for a complete message frame.
+    /// See [`Receiver::with_max_frame_buffer`].
+    max_frame_buffer: usize,
+    /// Total bytes fed into `decoder` for the current in-progress frame, tracked independently
+    /// of `buffer.buffered_len()`. `decode_frame` consumes bytes out of the `SegmentedBuf` as
+    /// it reads the prelude, even when it reports `DecodedFrame::Incomplete`, so
+    /// `buffered_len()` alone can wildly undercount how much a misbehaving server has made us
+    /// buffer for a single frame. Reset once a frame completes (to whatever's left over for the
+    /// next one); otherwise accumulated as each new chunk is buffered.
+    in_flight_frame_bytes: usize,
+    /// Whether to keep a rolling copy of the current in-progress frame's bytes around so that
+    /// decode failures can surface them. See [`Receiver::with_raw_capture`].
+    raw_capture: bool,
+    /// Cooperative stop signal shared with any handles returned by [`Receiver::stop_handle`].
+    stop: StopHandle,
     _phantom: PhantomData<E>,
 }
 
@@ -143,14 +387,93 @@ impl<T, E> Receiver<T, E> {
             buffer: RecvBuf::Empty,
             body,
             buffered_message: None,
+            decompression: None,
+            max_frame_buffer: DEFAULT_MAX_FRAME_BUFFER_BYTES,
+            in_flight_frame_bytes: 0,
+            raw_capture: false,
+            stop: StopHandle::default(),
             _phantom: Default::default(),
         }
     }
 
-    fn unmarshall(&self, message: Message) -> Result<Option<T>, SdkError<E, RawMessage>> {
+    /// Returns a cloneable handle that can be used to cooperatively stop this `Receiver` from
+    /// another task. See [`StopHandle::stop`].
+    pub fn stop_handle(&self) -> StopHandle {
+        self.stop.clone()
+    }
+
+    /// Sets the maximum number of bytes that will be buffered while waiting for a complete
+    /// message frame to be decoded, guarding against unbounded memory growth from a buggy or
+    /// malicious server. Once exceeded, `recv`/`poll_next` fail with
+    /// `SdkError::ResponseError` carrying [`Error::BufferLimitExceeded`].
+    ///
+    /// Defaults to [`DEFAULT_MAX_FRAME_BUFFER_BYTES`].
+    pub fn with_max_frame_buffer(mut self, bytes: usize) -> Self {
+        self.max_frame_buffer = bytes;
+        self
+    }
+
+    /// Enables capturing the raw bytes of the current in-progress message frame, bounded by
+    /// the configured max frame buffer, so that a decode failure's [`SdkError::ResponseError`]
+    /// carries the offending bytes via `RawMessage::Invalid(Some(..))` instead of `None`.
+    /// The captured bytes are those still buffered when the failing decode attempt began:
+    /// bytes the decoder already consumed for the frame on an earlier attempt (such as its
+    /// prelude) are not included, and bytes of later frames buffered after it may be.
+    /// Disabled by default, since it keeps a rolling copy of the buffered-but-undecoded data
+    /// around for every message.
+    pub fn with_raw_capture(mut self) -> Self {
+        self.raw_capture = true;
+        self
+    }
+
+    /// Enables transparent decompression of message payloads whose `content-encoding` header
+    /// matches one of `encodings`. Messages without a content-encoding header are passed
+    /// through unchanged; messages with an unrecognized or disabled content-encoding will
+    /// result in an `Err(SdkError::ResponseError)` carrying
+    /// [`Error::UnsupportedContentEncoding`].
+    pub fn with_payload_decompression(
+        mut self,
+        encodings: impl IntoIterator<Item = ContentEncoding>,
+    ) -> Self {
+        self.decompression = Some(encodings.into_iter().collect());
+        self
+    }
+
+    /// Decompresses `message`'s payload in place if decompression is enabled and the message
+    /// carries a recognized, enabled content-encoding. Messages without a content-encoding
+    /// header are passed through unchanged.
+    fn decompress_if_needed(&self, message: Message) -> Result<Message, SdkError<E, RawMessage>> {
+        let enabled = match &self.decompression {
+            Some(enabled) => enabled,
+            None => return Ok(message),
+        };
+        let encoding = match content_encoding_header(&message) {
+            Some(value) => value.to_string(),
+            None => return Ok(message),
+        };
+        let content_encoding = match ContentEncoding::from_header_value(&encoding) {
+            Some(content_encoding) if enabled.contains(&content_encoding) => content_encoding,
+            _ => {
+                return Err(SdkError::ResponseError {
+                    err: Error::UnsupportedContentEncoding(encoding).into(),
+                    raw: RawMessage::Decoded(message),
+                });
+            }
+        };
+        match content_encoding.decompress(&message.payload()[..]) {
+            Ok(decompressed) => Ok(strip_content_encoding(&message, decompressed.into())),
+            Err(err) => Err(SdkError::ResponseError {
+                err: Error::DecompressionFailed(err).into(),
+                raw: RawMessage::Decoded(message),
+            }),
+        }
+    }
+
+    fn unmarshall(&self, message: Message) -> Result<T, SdkError<E, RawMessage>> {
+        let message = self.decompress_if_needed(message)?;
         match self.unmarshaller.unmarshall(&message) {
             Ok(unmarshalled) => match unmarshalled {
-                UnmarshalledMessage::Event(event) => Ok(Some(event)),
+                UnmarshalledMessage::Event(event) => Ok(event),
                 UnmarshalledMessage::Error(err) => Err(SdkError::ServiceError {
                     err,
                     raw: RawMessage::Decoded(message),
@@ -163,55 +486,126 @@ impl<T, E> Receiver<T, E> {
         }
     }
 
-    async fn buffer_next_chunk(&mut self) -> Result<(), SdkError<E, RawMessage>> {
-        if !self.buffer.is_eos() {
-            let next_chunk = self
-                .body
-                .data()
-                .await
-                .transpose()
-                .map_err(|err| SdkError::DispatchFailure(ConnectorError::io(err)))?;
-            let buffer = mem::replace(&mut self.buffer, RecvBuf::Empty);
-            if let Some(chunk) = next_chunk {
-                self.buffer = buffer.with_partial(chunk);
-            } else {
-                self.buffer = buffer.ended();
-            }
+    /// Polls the underlying body for the next chunk of bytes and feeds it into `self.buffer`.
+    ///
+    /// Returns `Poll::Ready(Ok(()))` once a chunk (or EOS) has been buffered. `self.buffer`
+    /// is left untouched, and this is a no-op, if the stream has already reached EOS.
+    fn poll_buffer_next_chunk(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), SdkError<E, RawMessage>>> {
+        if self.buffer.is_eos() {
+            return Poll::Ready(Ok(()));
+        }
+        // Race the body read against the cooperative stop signal: if a stop was requested, end
+        // the stream here rather than polling the body for another chunk. The waker is
+        // registered *before* re-checking the flag so that a `stop()` landing between the
+        // check and us parking on `body.poll_data` below still wakes this task immediately,
+        // instead of leaving it parked until the body produces some unrelated event.
+        self.stop.register(cx);
+        if self.stop.is_stopped() {
+            self.buffer = mem::replace(&mut self.buffer, RecvBuf::Empty).stopped();
+            return Poll::Ready(Ok(()));
         }
-        Ok(())
+        let next_chunk = match Pin::new(&mut self.body).poll_data(cx) {
+            Poll::Ready(next_chunk) => next_chunk,
+            Poll::Pending => return Poll::Pending,
+        };
+        let next_chunk = match next_chunk
+            .transpose()
+            .map_err(|err| SdkError::DispatchFailure(ConnectorError::io(err)))
+        {
+            Ok(next_chunk) => next_chunk,
+            Err(err) => return Poll::Ready(Err(err)),
+        };
+        let buffer = mem::replace(&mut self.buffer, RecvBuf::Empty);
+        if let Some(chunk) = next_chunk {
+            self.in_flight_frame_bytes += chunk.len();
+            self.buffer = buffer.with_partial(chunk);
+        } else {
+            self.buffer = buffer.ended();
+        }
+        Poll::Ready(Ok(()))
     }
 
-    async fn next_message(&mut self) -> Result<Option<Message>, SdkError<E, RawMessage>> {
+    /// Polls for the next fully decoded frame, driving the underlying body forward as needed.
+    ///
+    /// Returns `Poll::Ready(Ok(None))` once the stream has cleanly ended, and an error if the
+    /// stream ended with a partial frame still buffered.
+    fn poll_next_message(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<Message>, SdkError<E, RawMessage>>> {
         while !self.buffer.is_eos() {
             if self.buffer.has_data() {
-                if let DecodedFrame::Complete(message) = self
-                    .decoder
-                    .decode_frame(self.buffer.buffered())
-                    .map_err(|err| SdkError::ResponseError {
+                // Snapshot the in-progress frame's bytes before decoding consumes the buffer, so
+                // a decode failure can still surface them for diagnostics. Cloning a
+                // `SegmentedBuf<Bytes>` is cheap since it only bumps `Bytes` refcounts.
+                let captured = self.raw_capture.then(|| self.buffer.buffered().clone());
+                let decoded = self.decoder.decode_frame(self.buffer.buffered()).map_err(
+                    |err| SdkError::ResponseError {
                         err: Box::new(err),
-                        raw: RawMessage::Invalid(None), // the buffer has been consumed
-                    })?
-                {
-                    return Ok(Some(message));
+                        raw: RawMessage::Invalid(captured.map(|mut captured| {
+                            let len = captured.remaining().min(self.max_frame_buffer);
+                            captured.copy_to_bytes(len)
+                        })),
+                    },
+                );
+                match decoded {
+                    Ok(DecodedFrame::Complete(message)) => {
+                        // The frame boundary has passed; whatever's left in `buffer` belongs to
+                        // the *next* frame and hasn't been handed to the decoder yet.
+                        self.in_flight_frame_bytes = self.buffer.buffered_len();
+                        return Poll::Ready(Ok(Some(message)));
+                    }
+                    Ok(DecodedFrame::Incomplete) => {
+                        // The decoder drained every complete frame it could find in the
+                        // buffered data and still needs more before it can make progress. Only
+                        // now do we know the buffer can't shrink on its own, so this is the
+                        // right point to enforce the limit rather than right after buffering a
+                        // chunk (which would false-positive on e.g. a single read that happens
+                        // to coalesce several complete, trivially-decodable frames). We check
+                        // `in_flight_frame_bytes` rather than `buffer.buffered_len()` because
+                        // `decode_frame` consumes bytes out of the `SegmentedBuf` as it reads
+                        // the prelude even when it reports `Incomplete`, so the buffer's
+                        // remaining length alone can silently undercount how much data a
+                        // misbehaving server has made us hold onto for this frame.
+                        let buffered = self.in_flight_frame_bytes;
+                        if !self.buffer.is_eos() && buffered > self.max_frame_buffer {
+                            return Poll::Ready(Err(SdkError::ResponseError {
+                                err: Error::BufferLimitExceeded {
+                                    limit: self.max_frame_buffer,
+                                    buffered,
+                                }
+                                .into(),
+                                raw: self.buffer.buffered().into(),
+                            }));
+                        }
+                    }
+                    Err(err) => return Poll::Ready(Err(err)),
                 }
             }
 
-            self.buffer_next_chunk().await?;
+            match self.poll_buffer_next_chunk(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
         }
         if self.buffer.has_data() {
-            return Err(SdkError::ResponseError {
+            return Poll::Ready(Err(SdkError::ResponseError {
                 err: Error::UnexpectedEndOfStream.into(),
                 raw: self.buffer.buffered().into(),
-            });
+            }));
         }
-        Ok(None)
+        Poll::Ready(Ok(None))
     }
 
     /// Tries to receive the initial response message that has `:event-type` of `initial-response`.
     /// If a different event type is received, then it is buffered and `Ok(None)` is returned.
     #[doc(hidden)]
     pub async fn try_recv_initial(&mut self) -> Result<Option<Message>, SdkError<E, RawMessage>> {
-        if let Some(message) = self.next_message().await? {
+        if let Some(message) = std::future::poll_fn(|cx| self.poll_next_message(cx)).await? {
             if let Some(event_type) = message
                 .headers()
                 .iter()
@@ -237,21 +631,38 @@ impl<T, E> Receiver<T, E> {
     /// it returns an `Ok(None)`. If there is a transport layer error, it will return
     /// `Err(SdkError::DispatchFailure)`. Service-modeled errors will be a part of the returned
     /// messages.
+    ///
+    /// This is a thin wrapper over the [`Stream`] implementation on `Receiver`.
     pub async fn recv(&mut self) -> Result<Option<T>, SdkError<E, RawMessage>> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx))
+            .await
+            .transpose()
+    }
+}
+
+// No field is structurally pinned, so `Receiver` is `Unpin` regardless of `E`
+// (without this, `PhantomData<E>` would tie it to `E: Unpin`).
+impl<T, E> Unpin for Receiver<T, E> {}
+
+impl<T, E> Stream for Receiver<T, E> {
+    type Item = Result<T, SdkError<E, RawMessage>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         if let Some(buffered) = self.buffered_message.take() {
-            return self.unmarshall(buffered);
+            return Poll::Ready(Some(self.unmarshall(buffered)));
         }
-        if let Some(message) = self.next_message().await? {
-            self.unmarshall(message)
-        } else {
-            Ok(None)
+        match self.poll_next_message(cx) {
+            Poll::Ready(Ok(Some(message))) => Poll::Ready(Some(self.unmarshall(message))),
+            Poll::Ready(Ok(None)) => Poll::Ready(None),
+            Poll::Ready(Err(err)) => Poll::Ready(Some(Err(err))),
+            Poll::Pending => Poll::Pending,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Receiver, UnmarshallMessage};
+    use super::{ContentEncoding, RawMessage, Receiver, UnmarshallMessage};
     use crate::body::SdkBody;
     use crate::result::SdkError;
     use aws_smithy_eventstream::error::Error as EventStreamError;
@@ -277,6 +688,18 @@ mod tests {
         buffer.into()
     }
 
+    fn encode_message_with_content_encoding(message: &str, content_encoding: &str) -> Bytes {
+        let mut buffer = Vec::new();
+        Message::new(Bytes::copy_from_slice(message.as_bytes()))
+            .add_header(Header::new(
+                ":content-encoding",
+                HeaderValue::String(content_encoding.to_string().into()),
+            ))
+            .write_to(&mut buffer)
+            .unwrap();
+        buffer.into()
+    }
+
     fn encode_message(message: &str) -> Bytes {
         let mut buffer = Vec::new();
         Message::new(Bytes::copy_from_slice(message.as_bytes()))
@@ -530,4 +953,136 @@ mod tests {
     async fn receiver_is_send() {
         assert_send::<Receiver<(), ()>>();
     }
+
+    #[tokio::test]
+    async fn receive_passes_through_content_encoding_when_decompression_disabled() {
+        let chunks: Vec<Result<_, IOError>> =
+            vec![Ok(encode_message_with_content_encoding("one", "gzip"))];
+        let chunk_stream = futures_util::stream::iter(chunks);
+        let body = SdkBody::from(Body::wrap_stream(chunk_stream));
+        let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body);
+        // Decompression wasn't enabled, so the payload is passed straight through to the
+        // unmarshaller as-is, despite its `gzip` content-encoding header.
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn receive_rejects_disabled_content_encoding() {
+        let chunks: Vec<Result<_, IOError>> =
+            vec![Ok(encode_message_with_content_encoding("one", "br"))];
+        let chunk_stream = futures_util::stream::iter(chunks);
+        let body = SdkBody::from(Body::wrap_stream(chunk_stream));
+        let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body)
+            .with_payload_decompression([ContentEncoding::Gzip]);
+        assert!(matches!(
+            receiver.recv().await,
+            Err(SdkError::ResponseError { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn receive_stops_cooperatively() {
+        let chunks: Vec<Result<_, IOError>> =
+            vec![Ok(encode_message("one")), Ok(encode_message("two"))];
+        let chunk_stream = futures_util::stream::iter(chunks);
+        let body = SdkBody::from(Body::wrap_stream(chunk_stream));
+        let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body);
+        let stop_handle = receiver.stop_handle();
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        stop_handle.stop();
+        // The second message was never read off the body, so stopping reports a clean
+        // end-of-stream rather than `Error::UnexpectedEndOfStream`.
+        assert_eq!(None, receiver.recv().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn receive_message_parse_failure_with_raw_capture() {
+        let chunks: Vec<Result<_, IOError>> = vec![
+            Ok(encode_message("one")),
+            Ok(Bytes::from_static(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0])),
+        ];
+        let chunk_stream = futures_util::stream::iter(chunks);
+        let body = SdkBody::from(Body::wrap_stream(chunk_stream));
+        let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body)
+            .with_raw_capture();
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        match receiver.recv().await {
+            Err(SdkError::ResponseError { raw, .. }) => match raw {
+                RawMessage::Invalid(Some(captured)) => {
+                    assert_eq!(&[0u8; 12][..], &captured[..]);
+                }
+                RawMessage::Invalid(None) => panic!("expected captured raw bytes, got none"),
+                RawMessage::Decoded(_) => panic!("expected captured raw bytes, got a decoded message"),
+            },
+            Ok(_) => panic!("expected a ResponseError, got Ok"),
+            Err(_) => panic!("expected a ResponseError, got a different SdkError variant"),
+        }
+    }
+
+    #[tokio::test]
+    async fn receive_enforces_max_frame_buffer() {
+        // A well-formed prelude (valid prelude CRC) declaring a `total_length` far larger than
+        // what's actually sent, so the decoder reports `DecodedFrame::Incomplete` rather than
+        // erroring out on a bad CRC. This lets the test exercise the buffer-limit check itself
+        // rather than an unrelated decode failure.
+        let oversized_prelude: &[u8] = &[0, 0, 1, 244, 0, 0, 0, 0, 19, 202, 250, 81];
+        let chunks: Vec<Result<_, IOError>> =
+            vec![Ok(Bytes::copy_from_slice(oversized_prelude))];
+        let chunk_stream = futures_util::stream::iter(chunks);
+        let body = SdkBody::from(Body::wrap_stream(chunk_stream));
+        let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body)
+            .with_max_frame_buffer(10);
+        assert!(matches!(
+            receiver.recv().await,
+            Err(SdkError::ResponseError { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn receive_does_not_false_positive_on_coalesced_complete_frames() {
+        // Two complete, trivially-decodable messages delivered in a single chunk whose
+        // combined size exceeds `max_frame_buffer`. The limit must not trip here, since the
+        // decoder can fully drain both frames without ever needing more data.
+        let combined = Bytes::from([encode_message("one"), encode_message("two")].concat());
+        let limit = combined.len() - 1;
+        let chunks: Vec<Result<_, IOError>> = vec![Ok(combined)];
+        let chunk_stream = futures_util::stream::iter(chunks);
+        let body = SdkBody::from(Body::wrap_stream(chunk_stream));
+        let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body)
+            .with_max_frame_buffer(limit);
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        assert_eq!(
+            TestMessage("two".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        assert_eq!(None, receiver.recv().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn receive_via_stream() {
+        use futures_util::StreamExt;
+
+        let chunks: Vec<Result<_, IOError>> =
+            vec![Ok(encode_message("one")), Ok(encode_message("two"))];
+        let chunk_stream = futures_util::stream::iter(chunks);
+        let body = SdkBody::from(Body::wrap_stream(chunk_stream));
+        let receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body);
+        let messages: Vec<_> = receiver.collect().await;
+        assert_eq!(
+            vec![TestMessage("one".into()), TestMessage("two".into())],
+            messages.into_iter().collect::<Result<Vec<_>, _>>().unwrap()
+        );
+    }
 }